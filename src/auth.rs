@@ -1,4 +1,25 @@
-#[derive(Clone, Debug)]
+use base64::Engine;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Seconds before a token's `exp` that the connector re-issues it, so the swap
+/// happens while the old token is still valid for the in-flight handshake.
+const TOKEN_REFRESH_SKEW: Duration = Duration::from_secs(30);
+
+/// Future returned by a [`TokenProvider`], resolving to a fresh bearer token.
+pub type TokenFuture = Pin<Box<dyn Future<Output = String> + Send>>;
+
+/// Callback that issues a fresh Azure authorization token on demand.
+///
+/// Used for Entra ID / managed-identity scenarios where the token is not a
+/// subscription key but a short-lived JWT minted by an external authority. The
+/// connector re-invokes it shortly before the current token expires.
+pub type TokenProvider = Arc<dyn Fn() -> TokenFuture + Send + Sync>;
+
+#[derive(Clone)]
 /// Auth struct, used to authenticate with Azure Speech Services.
 pub enum Auth {
     /// Authenticate against Azure cloud using `region` and `subscription` key.
@@ -11,6 +32,20 @@ pub enum Auth {
     /// - ws://localhost:5000
     /// - wss://my-gateway.example.com:5000
     Host { host: String },
+    /// Authenticate with a short-lived authorization token (a bearer JWT).
+    ///
+    /// Azure issues these from the STS endpoint
+    /// `https://{region}.api.cognitive.microsoft.com/sts/v1.0/issueToken` and they
+    /// travel as the `Authorization: Bearer …` header on the websocket handshake.
+    AuthorizationToken { region: String, token: String },
+    /// Authenticate with a caller-supplied token provider (Entra ID /
+    /// managed identity). The connector invokes `provider` to obtain the
+    /// initial token and re-invokes it before expiry to keep long-lived
+    /// streaming sessions alive.
+    TokenProvider {
+        region: String,
+        provider: TokenProvider,
+    },
 }
 
 impl Auth {
@@ -27,6 +62,32 @@ impl Auth {
         Auth::Host { host: host.into() }
     }
 
+    /// Create a new Auth instance from an already-issued authorization token.
+    ///
+    /// The token is a bearer JWT (valid roughly ten minutes) that the connector
+    /// sends as the `Authorization: Bearer …` header on the handshake.
+    pub fn from_authorization_token(region: impl Into<String>, token: impl Into<String>) -> Self {
+        Auth::AuthorizationToken {
+            region: region.into(),
+            token: token.into(),
+        }
+    }
+
+    /// Create a new Auth instance from a token provider.
+    ///
+    /// `provider` is invoked to mint a bearer token; the connector re-invokes it
+    /// shortly before expiry and swaps the handshake header on reconnect.
+    pub fn from_token_provider<F, Fut>(region: impl Into<String>, provider: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = String> + Send + 'static,
+    {
+        Auth::TokenProvider {
+            region: region.into(),
+            provider: Arc::new(move || Box::pin(provider()) as TokenFuture),
+        }
+    }
+
     pub(crate) fn subscription_region(&self) -> Option<(&str, &str)> {
         match self {
             Auth::Subscription {
@@ -36,4 +97,173 @@ impl Auth {
             _ => None,
         }
     }
+
+    /// The Azure region associated with this auth, when one is known.
+    pub(crate) fn region(&self) -> Option<&str> {
+        match self {
+            Auth::Subscription { region, .. }
+            | Auth::AuthorizationToken { region, .. }
+            | Auth::TokenProvider { region, .. } => Some(region.as_str()),
+            Auth::Host { .. } => None,
+        }
+    }
+
+    /// The bearer token to present on the handshake, when this auth already
+    /// carries one. Providers resolve lazily, so they return `None` here.
+    pub(crate) fn authorization_token(&self) -> Option<&str> {
+        match self {
+            Auth::AuthorizationToken { token, .. } => Some(token.as_str()),
+            _ => None,
+        }
+    }
+
+    /// The token provider to invoke for (re-)issuing bearer tokens, if any.
+    pub(crate) fn token_provider(&self) -> Option<&TokenProvider> {
+        match self {
+            Auth::TokenProvider { provider, .. } => Some(provider),
+            _ => None,
+        }
+    }
+
+    /// The STS endpoint that mints authorization tokens for this auth's region.
+    ///
+    /// A `Subscription` key is exchanged for a bearer JWT by POSTing to this URL
+    /// with the `Ocp-Apim-Subscription-Key` header; `None` for `Host` auth, which
+    /// carries no region.
+    pub fn issue_token_endpoint(&self) -> Option<String> {
+        self.region().map(|region| {
+            format!("https://{region}.api.cognitive.microsoft.com/sts/v1.0/issueToken")
+        })
+    }
+
+    /// Resolve the bearer token to present on the websocket handshake, if this
+    /// auth uses one.
+    ///
+    /// [`Auth::AuthorizationToken`] returns its token directly; [`Auth::TokenProvider`]
+    /// invokes the provider to mint a fresh one (Entra ID / managed identity);
+    /// subscription and host auth carry no bearer and return `None`.
+    pub async fn handshake_bearer_token(&self) -> Option<String> {
+        if let Some(token) = self.authorization_token() {
+            return Some(token.to_string());
+        }
+        match self.token_provider() {
+            Some(provider) => Some(provider().await),
+            None => None,
+        }
+    }
+
+    /// How long the connector should wait before re-issuing `token`, derived from
+    /// the JWT `exp` claim less [`TOKEN_REFRESH_SKEW`].
+    ///
+    /// Returns [`Duration::ZERO`] when the token is already within the skew window
+    /// (refresh now) and `None` when no expiry can be read from the token.
+    pub fn refresh_delay(token: &str) -> Option<Duration> {
+        let expiry = Self::token_expiry(token)?;
+        let deadline = expiry.checked_sub(TOKEN_REFRESH_SKEW).unwrap_or(expiry);
+        Some(
+            deadline
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO),
+        )
+    }
+
+    /// Parse the expiry (`exp`) claim out of a bearer JWT without verifying it.
+    fn token_expiry(token: &str) -> Option<SystemTime> {
+        let payload = token.split('.').nth(1)?;
+        let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(payload)
+            .ok()?;
+        let claims: serde_json::Value = serde_json::from_slice(&decoded).ok()?;
+        let exp = claims.get("exp")?.as_u64()?;
+        Some(UNIX_EPOCH + Duration::from_secs(exp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a minimal unsigned JWT carrying only an `exp` claim.
+    fn jwt_with_exp(exp: u64) -> String {
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(format!("{{\"exp\":{exp}}}"));
+        format!("header.{payload}.sig")
+    }
+
+    #[test]
+    fn test_issue_token_endpoint_uses_region() {
+        let auth = Auth::from_authorization_token("westus", "tok");
+        assert_eq!(
+            auth.issue_token_endpoint().as_deref(),
+            Some("https://westus.api.cognitive.microsoft.com/sts/v1.0/issueToken")
+        );
+        assert!(Auth::from_host("ws://localhost:5000")
+            .issue_token_endpoint()
+            .is_none());
+    }
+
+    #[test]
+    fn test_refresh_delay_is_positive_before_expiry() {
+        let exp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 600;
+        let delay = Auth::refresh_delay(&jwt_with_exp(exp)).expect("delay");
+        assert!(delay > Duration::ZERO);
+        assert!(delay <= Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_refresh_delay_is_zero_past_expiry() {
+        assert_eq!(Auth::refresh_delay(&jwt_with_exp(0)), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_refresh_delay_none_without_exp() {
+        assert!(Auth::refresh_delay("not-a-jwt").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_bearer_token_variants() {
+        assert_eq!(
+            Auth::from_authorization_token("westus", "tok")
+                .handshake_bearer_token()
+                .await
+                .as_deref(),
+            Some("tok")
+        );
+        let provider = Auth::from_token_provider("westus", || async { "minted".to_string() });
+        assert_eq!(
+            provider.handshake_bearer_token().await.as_deref(),
+            Some("minted")
+        );
+        assert!(Auth::from_subscription("westus", "key")
+            .handshake_bearer_token()
+            .await
+            .is_none());
+    }
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Subscription { region, .. } => f
+                .debug_struct("Subscription")
+                .field("region", region)
+                .field("subscription", &"<redacted>")
+                .finish(),
+            Auth::Host { host } => f.debug_struct("Host").field("host", host).finish(),
+            Auth::AuthorizationToken { region, .. } => f
+                .debug_struct("AuthorizationToken")
+                .field("region", region)
+                .field("token", &"<redacted>")
+                .finish(),
+            Auth::TokenProvider { region, .. } => f
+                .debug_struct("TokenProvider")
+                .field("region", region)
+                .field("provider", &"<fn>")
+                .finish(),
+        }
+    }
 }
@@ -0,0 +1,277 @@
+use crate::synthesizer::StreamingRequest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// The metadata emitted alongside synthesized audio, persisted so a cache hit
+/// can replay the exact same event stream offline.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CachedMetadata {
+    pub word_boundaries: Vec<serde_json::Value>,
+    pub sentence_boundaries: Vec<serde_json::Value>,
+    pub visemes: Vec<serde_json::Value>,
+    pub bookmarks: Vec<serde_json::Value>,
+}
+
+/// A cache entry: the raw audio bytes and the sidecar metadata.
+#[derive(Clone, Debug)]
+pub struct CachedEntry {
+    pub audio: Vec<u8>,
+    pub metadata: CachedMetadata,
+}
+
+/// Content-addressed, byte-budgeted on-disk cache for synthesized audio.
+///
+/// Each entry is stored as two files in the cache directory — `{key}.bin` for
+/// the raw audio and `{key}.json` for the emitted metadata — keyed by a
+/// SHA-256 over the normalized input, resolved voice/locale, output format and
+/// per-request prosody. A byte budget is enforced with LRU eviction.
+#[derive(Debug)]
+pub struct Cache {
+    dir: PathBuf,
+    max_bytes: u64,
+    index: Mutex<Index>,
+}
+
+#[derive(Debug, Default)]
+struct Index {
+    entries: HashMap<String, EntryMeta>,
+    total_bytes: u64,
+}
+
+#[derive(Debug)]
+struct EntryMeta {
+    bytes: u64,
+    accessed: SystemTime,
+}
+
+impl Cache {
+    /// Open (creating if necessary) a cache rooted at `path` with a `max_bytes`
+    /// budget, rebuilding the LRU index from whatever is already on disk.
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64) -> crate::Result<Self> {
+        let dir = path.into();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| crate::Error::InternalError(format!("cache dir: {e}")))?;
+
+        let mut index = Index::default();
+        for entry in std::fs::read_dir(&dir)
+            .map_err(|e| crate::Error::InternalError(format!("cache scan: {e}")))?
+        {
+            let entry = entry.map_err(|e| crate::Error::InternalError(e.to_string()))?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("bin") {
+                continue;
+            }
+            let key = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(key) => key.to_string(),
+                None => continue,
+            };
+            let meta = entry
+                .metadata()
+                .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+            let accessed = meta.accessed().or_else(|_| meta.modified()).unwrap_or(
+                SystemTime::UNIX_EPOCH,
+            );
+            // Count the sidecar JSON too, so the budget matches what `insert`
+            // records and doesn't drift across reopens.
+            let sidecar_len = std::fs::metadata(dir.join(format!("{key}.json")))
+                .map(|m| m.len())
+                .unwrap_or(0);
+            let bytes = meta.len() + sidecar_len;
+            index.total_bytes += bytes;
+            index.entries.insert(key, EntryMeta { bytes, accessed });
+        }
+
+        Ok(Self {
+            dir,
+            max_bytes,
+            index: Mutex::new(index),
+        })
+    }
+
+    /// Look up an entry, loading its audio and metadata and bumping its access
+    /// time so it survives eviction.
+    pub fn get(&self, key: &str) -> Option<CachedEntry> {
+        let audio = std::fs::read(self.audio_path(key)).ok()?;
+        let metadata = std::fs::read(self.meta_path(key))
+            .ok()
+            .and_then(|raw| serde_json::from_slice(&raw).ok())
+            .unwrap_or_default();
+
+        if let Ok(mut index) = self.index.lock() {
+            if let Some(meta) = index.entries.get_mut(key) {
+                meta.accessed = SystemTime::now();
+            }
+        }
+
+        Some(CachedEntry { audio, metadata })
+    }
+
+    /// Store an entry, evicting least-recently-used entries first whenever the
+    /// new total would exceed the byte budget.
+    pub fn insert(&self, key: &str, audio: &[u8], metadata: &CachedMetadata) -> crate::Result<()> {
+        let meta_bytes = serde_json::to_vec(metadata)
+            .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+        let size = audio.len() as u64 + meta_bytes.len() as u64;
+
+        self.evict_until_fits(key, size);
+
+        std::fs::write(self.audio_path(key), audio)
+            .map_err(|e| crate::Error::InternalError(format!("cache write: {e}")))?;
+        std::fs::write(self.meta_path(key), &meta_bytes)
+            .map_err(|e| crate::Error::InternalError(format!("cache write: {e}")))?;
+
+        if let Ok(mut index) = self.index.lock() {
+            if let Some(old) = index.entries.insert(
+                key.to_string(),
+                EntryMeta {
+                    bytes: size,
+                    accessed: SystemTime::now(),
+                },
+            ) {
+                index.total_bytes -= old.bytes;
+            }
+            index.total_bytes += size;
+        }
+
+        Ok(())
+    }
+
+    /// Evict oldest entries until `incoming` bytes would fit under the budget.
+    ///
+    /// When `key` names an entry already present (an overwrite), its current size
+    /// is discounted first so we don't over-evict for bytes about to be replaced.
+    fn evict_until_fits(&self, key: &str, incoming: u64) {
+        let mut index = match self.index.lock() {
+            Ok(index) => index,
+            Err(_) => return,
+        };
+        let replaced = index.entries.get(key).map(|meta| meta.bytes).unwrap_or(0);
+        while index.total_bytes.saturating_sub(replaced) + incoming > self.max_bytes {
+            let victim = index
+                .entries
+                .iter()
+                .filter(|(k, _)| k.as_str() != key)
+                .min_by_key(|(_, meta)| meta.accessed)
+                .map(|(k, _)| k.clone());
+            let key = match victim {
+                Some(key) => key,
+                None => break,
+            };
+            if let Some(meta) = index.entries.remove(&key) {
+                index.total_bytes = index.total_bytes.saturating_sub(meta.bytes);
+            }
+            let _ = std::fs::remove_file(self.audio_path(&key));
+            let _ = std::fs::remove_file(self.meta_path(&key));
+        }
+    }
+
+    fn audio_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.bin"))
+    }
+
+    fn meta_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+/// Compute the content-addressed cache key over the inputs that determine the
+/// synthesized audio: the normalized text/SSML, the resolved voice and locale,
+/// the output format, and the per-request prosody fields.
+pub(crate) fn cache_key(
+    input: &str,
+    voice: &str,
+    locale: &str,
+    audio_format: &str,
+    request: &StreamingRequest,
+) -> String {
+    let mut hasher = Sha256::new();
+    // Field separators keep distinct inputs from colliding after concatenation.
+    for field in [input.trim(), voice, locale, audio_format] {
+        hasher.update(field.as_bytes());
+        hasher.update([0u8]);
+    }
+    for field in [
+        &request.pitch,
+        &request.rate,
+        &request.volume,
+        &request.style,
+        &request.custom_lexicon_url,
+    ] {
+        hasher.update(field.as_deref().unwrap_or_default().as_bytes());
+        hasher.update([0u8]);
+    }
+    if let Some(temperature) = request.temperature {
+        hasher.update(temperature.to_le_bytes());
+    }
+    hasher.update([0u8]);
+
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_is_stable_and_sensitive() {
+        let req = StreamingRequest::new();
+        let a = cache_key("Hello", "en-US-JennyNeural", "en-US", "audio-16khz", &req);
+        let b = cache_key("Hello", "en-US-JennyNeural", "en-US", "audio-16khz", &req);
+        let c = cache_key("Goodbye", "en-US-JennyNeural", "en-US", "audio-16khz", &req);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    fn temp_cache_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("azure-speech-cache-test-{tag}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_budget_stable_across_reopen() {
+        let dir = temp_cache_dir("reopen");
+        let cache = Cache::new(&dir, 1 << 20).unwrap();
+        cache
+            .insert("k", &[0u8; 100], &CachedMetadata::default())
+            .unwrap();
+        let before = cache.index.lock().unwrap().total_bytes;
+        drop(cache);
+
+        // Reopening must recover the same total (audio + sidecar), not just .bin.
+        let reopened = Cache::new(&dir, 1 << 20).unwrap();
+        assert_eq!(reopened.index.lock().unwrap().total_bytes, before);
+    }
+
+    #[test]
+    fn test_overwrite_does_not_over_evict() {
+        let dir = temp_cache_dir("overwrite");
+        // Each entry is 80 audio bytes + a 75-byte sidecar; the budget holds two
+        // but not three, so overwriting one must keep the other.
+        let cache = Cache::new(&dir, 400).unwrap();
+        cache
+            .insert("a", &[1u8; 80], &CachedMetadata::default())
+            .unwrap();
+        cache
+            .insert("b", &[2u8; 80], &CachedMetadata::default())
+            .unwrap();
+        cache
+            .insert("a", &[3u8; 80], &CachedMetadata::default())
+            .unwrap();
+        assert!(cache.get("b").is_some(), "overwriting 'a' evicted 'b'");
+    }
+
+    #[test]
+    fn test_cache_key_tracks_prosody() {
+        let plain = StreamingRequest::new();
+        let loud = StreamingRequest::new().volume("+20%");
+        assert_ne!(
+            cache_key("Hi", "voice", "en-US", "fmt", &plain),
+            cache_key("Hi", "voice", "en-US", "fmt", &loud)
+        );
+    }
+}
@@ -36,11 +36,16 @@
 //! ```
 
 mod audio_format;
+mod cache;
 mod client;
 mod config;
 mod event;
 mod language;
 pub mod message;
+#[cfg(feature = "playback")]
+mod player;
+mod proxy;
+mod reconnect;
 mod session;
 mod text_stream;
 mod utils;
@@ -50,11 +55,16 @@ mod callback;
 pub mod ssml;
 
 pub use audio_format::*;
+pub use cache::*;
 pub use callback::*;
 pub use client::*;
 pub use config::*;
 pub use event::*;
 pub use language::*;
+#[cfg(feature = "playback")]
+pub use player::*;
+pub use proxy::*;
+pub use reconnect::*;
 pub use text_stream::*;
 pub use voice::*;
 
@@ -0,0 +1,411 @@
+//! Optional audio playback sink.
+//!
+//! Consumes the synthesizer's audio event stream and plays it to the default
+//! output device via `rodio`, modelled after a media-session/track-queue
+//! controller. Enabled with the `playback` feature.
+
+use crate::synthesizer::AudioFormat;
+use std::collections::VecDeque;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, Mutex};
+
+/// How often a position update is emitted while a track is playing.
+const POSITION_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How the sink should turn a track's frames into playable audio, resolved from
+/// `config.audio_format` up front so an unsupported format fails fast.
+#[derive(Clone, Copy, Debug)]
+enum Decoding {
+    /// Raw little-endian 16-bit PCM, streamed frame-by-frame for low latency.
+    Pcm { sample_rate: u32, channels: u16 },
+    /// A container/compressed stream (WAV/MP3/OGG/Opus) decoded by rodio once the
+    /// track's frames have all arrived.
+    Compressed,
+}
+
+impl Decoding {
+    /// Resolve the decoding strategy for `format`, erroring for formats the sink
+    /// cannot play so the player never silently produces noise.
+    fn resolve(format: &AudioFormat) -> crate::Result<Self> {
+        let name = format.as_str();
+        let lower = name.to_ascii_lowercase();
+
+        if lower.contains("mulaw") || lower.contains("alaw") {
+            return Err(crate::Error::InternalError(format!(
+                "unsupported companded audio format for playback: {name}"
+            )));
+        }
+        // Container/compressed payloads carry their own sample-rate/channel info.
+        if ["mp3", "ogg", "opus", "webm", "riff"]
+            .iter()
+            .any(|codec| lower.contains(codec))
+        {
+            return Ok(Decoding::Compressed);
+        }
+        if lower.contains("pcm") {
+            if !lower.contains("16bit") {
+                return Err(crate::Error::InternalError(format!(
+                    "unsupported PCM sample width for playback: {name}"
+                )));
+            }
+            let sample_rate = parse_sample_rate(&lower).ok_or_else(|| {
+                crate::Error::InternalError(format!(
+                    "cannot determine sample rate for audio format: {name}"
+                ))
+            })?;
+            let channels = if lower.contains("stereo") { 2 } else { 1 };
+            return Ok(Decoding::Pcm {
+                sample_rate,
+                channels,
+            });
+        }
+
+        Err(crate::Error::InternalError(format!(
+            "unsupported audio format for playback: {name}"
+        )))
+    }
+}
+
+/// Extract the sample rate from an Azure format name, e.g. `24khz` -> 24000,
+/// `44100hz` -> 44100.
+fn parse_sample_rate(lower: &str) -> Option<u32> {
+    for part in lower.split(|c: char| !c.is_ascii_alphanumeric()) {
+        if let Some(khz) = part.strip_suffix("khz") {
+            if let Ok(n) = khz.parse::<u32>() {
+                return Some(n * 1000);
+            }
+        } else if let Some(hz) = part.strip_suffix("hz") {
+            if let Ok(n) = hz.parse::<u32>() {
+                return Some(n);
+            }
+        }
+    }
+    None
+}
+
+/// A queued unit of audio, fed frame-by-frame as it streams in from a
+/// `synthesize` result so playback can start before synthesis completes.
+pub struct Track {
+    pub request_id: String,
+    /// Incoming audio frames for this track; closed when synthesis finishes.
+    pub frames: mpsc::Receiver<Vec<u8>>,
+    /// Word-boundary / viseme metadata, each tagged with its audio offset so it
+    /// can be correlated to the current playback position for captions/lip-sync.
+    pub metadata: mpsc::Receiver<TrackMetadata>,
+}
+
+/// A single timed metadata cue carried alongside a track's audio.
+#[derive(Clone, Debug)]
+pub struct TrackMetadata {
+    pub offset: Duration,
+    pub payload: serde_json::Value,
+}
+
+/// Transport command accepted by the running player.
+#[derive(Debug)]
+enum Command {
+    Enqueue(String),
+    Play,
+    Pause,
+    Resume,
+    Stop,
+    Skip,
+}
+
+/// Player-state events fired alongside the existing synthesis events.
+#[derive(Clone, Debug)]
+pub enum PlayerEvent {
+    Started { request_id: String },
+    Paused,
+    Resumed,
+    /// Current playback position within the active track.
+    Position { request_id: String, offset: Duration },
+    /// A metadata cue for the active track, carrying its audio `offset`. It is
+    /// emitted as soon as it arrives, not when playback reaches `offset`.
+    Cue {
+        request_id: String,
+        offset: Duration,
+        payload: serde_json::Value,
+    },
+    Finished { request_id: String },
+    QueueEmpty,
+}
+
+/// Handle used to enqueue tracks and drive transport controls on the player.
+///
+/// Cloning yields another handle to the same player; events are observed via
+/// [`AudioPlayer::events`].
+#[derive(Clone)]
+pub struct AudioPlayer {
+    commands: mpsc::Sender<Command>,
+    tracks: Arc<Mutex<VecDeque<Track>>>,
+    events: broadcast::Sender<PlayerEvent>,
+}
+
+impl AudioPlayer {
+    /// Create a player for audio in `format`, spawning its playback task on the
+    /// current runtime.
+    pub fn new(format: AudioFormat) -> crate::Result<Self> {
+        let decoding = Decoding::resolve(&format)?;
+        let (commands_tx, commands_rx) = mpsc::channel(32);
+        let (events_tx, _) = broadcast::channel(256);
+        let tracks = Arc::new(Mutex::new(VecDeque::new()));
+
+        let player = Self {
+            commands: commands_tx,
+            tracks: tracks.clone(),
+            events: events_tx.clone(),
+        };
+
+        tokio::spawn(run(decoding, commands_rx, tracks, events_tx));
+        Ok(player)
+    }
+
+    /// Subscribe to player-state events.
+    pub fn events(&self) -> broadcast::Receiver<PlayerEvent> {
+        self.events.subscribe()
+    }
+
+    /// Enqueue a track for back-to-back playback.
+    pub async fn enqueue(&self, track: Track) -> crate::Result<()> {
+        let request_id = track.request_id.clone();
+        self.tracks.lock().await.push_back(track);
+        self.send(Command::Enqueue(request_id)).await
+    }
+
+    /// Begin (or ensure) playback of the queue.
+    pub async fn play(&self) -> crate::Result<()> {
+        self.send(Command::Play).await
+    }
+
+    /// Pause the active track, keeping the queue intact.
+    pub async fn pause(&self) -> crate::Result<()> {
+        self.send(Command::Pause).await
+    }
+
+    /// Resume a paused track.
+    pub async fn resume(&self) -> crate::Result<()> {
+        self.send(Command::Resume).await
+    }
+
+    /// Stop playback and clear the queue.
+    pub async fn stop(&self) -> crate::Result<()> {
+        self.send(Command::Stop).await
+    }
+
+    /// Skip to the next track in the queue.
+    pub async fn skip(&self) -> crate::Result<()> {
+        self.send(Command::Skip).await
+    }
+
+    async fn send(&self, command: Command) -> crate::Result<()> {
+        self.commands
+            .send(command)
+            .await
+            .map_err(|e| crate::Error::InternalError(format!("player stopped: {e}")))
+    }
+}
+
+/// The track currently being played, held across loop iterations so an incoming
+/// command never drops it.
+struct Active {
+    track: Track,
+    request_id: String,
+    /// Set once the track's frame channel has closed; the track finishes when the
+    /// sink has also drained.
+    frames_done: bool,
+    /// Set once the metadata channel has closed, so its select branch stops firing.
+    meta_done: bool,
+    /// Accumulated bytes for [`Decoding::Compressed`] tracks, decoded in one shot
+    /// once the frame channel closes; unused for raw PCM.
+    buffer: Vec<u8>,
+    /// The sink's lifetime position when this track was promoted. The sink is
+    /// reused across queued tracks, so reported offsets are taken relative to
+    /// this baseline to stay within the active track.
+    start_pos: Duration,
+}
+
+impl Active {
+    /// The active track's playback position, relative to its own start.
+    fn offset(&self, sink: &rodio::Sink) -> Duration {
+        sink.get_pos().saturating_sub(self.start_pos)
+    }
+}
+
+/// Playback task: owns the output device and drains the track queue, appending
+/// frames to the sink as they stream in and emitting transport/position events.
+async fn run(
+    decoding: Decoding,
+    mut commands: mpsc::Receiver<Command>,
+    tracks: Arc<Mutex<VecDeque<Track>>>,
+    events: broadcast::Sender<PlayerEvent>,
+) {
+    let (_stream, handle) = match rodio::OutputStream::try_default() {
+        Ok(output) => output,
+        Err(e) => {
+            tracing::error!(?e, "failed to open default output device");
+            return;
+        }
+    };
+    let sink = match rodio::Sink::try_new(&handle) {
+        Ok(sink) => sink,
+        Err(e) => {
+            tracing::error!(?e, "failed to create playback sink");
+            return;
+        }
+    };
+
+    let mut active: Option<Active> = None;
+
+    loop {
+        // Promote the head of the queue once the previous track is done.
+        if active.is_none() {
+            if let Some(track) = tracks.lock().await.pop_front() {
+                let request_id = track.request_id.clone();
+                let _ = events.send(PlayerEvent::Started {
+                    request_id: request_id.clone(),
+                });
+                active = Some(Active {
+                    track,
+                    request_id,
+                    frames_done: false,
+                    meta_done: false,
+                    buffer: Vec::new(),
+                    start_pos: sink.get_pos(),
+                });
+            }
+        }
+
+        match active.as_mut() {
+            // A track is playing: handle commands, incoming frames, cues and
+            // periodic position updates in the same select so none cancels the
+            // others and the track is never dropped mid-stream.
+            Some(current) => {
+                tokio::select! {
+                    command = commands.recv() => match command {
+                        Some(Command::Pause) => {
+                            sink.pause();
+                            let _ = events.send(PlayerEvent::Paused);
+                        }
+                        Some(Command::Resume) => {
+                            sink.play();
+                            let _ = events.send(PlayerEvent::Resumed);
+                        }
+                        Some(Command::Stop) => {
+                            sink.stop();
+                            tracks.lock().await.clear();
+                            active = None;
+                            let _ = events.send(PlayerEvent::QueueEmpty);
+                        }
+                        Some(Command::Skip) => {
+                            sink.stop();
+                            let _ = events.send(PlayerEvent::Finished {
+                                request_id: current.request_id.clone(),
+                            });
+                            active = None;
+                        }
+                        Some(Command::Play) => {
+                            if sink.is_paused() {
+                                sink.play();
+                                let _ = events.send(PlayerEvent::Resumed);
+                            }
+                        }
+                        Some(Command::Enqueue(_)) => {}
+                        None => break,
+                    },
+                    frame = current.track.frames.recv(), if !current.frames_done => match frame {
+                        Some(frame) => match decoding {
+                            // Raw PCM streams frame-by-frame for low latency.
+                            Decoding::Pcm { sample_rate, channels } => {
+                                sink.append(pcm_source(&frame, sample_rate, channels));
+                                let _ = events.send(PlayerEvent::Position {
+                                    request_id: current.request_id.clone(),
+                                    offset: current.offset(&sink),
+                                });
+                            }
+                            // Compressed payloads can't be decoded piecewise; buffer
+                            // until the stream closes.
+                            Decoding::Compressed => current.buffer.extend_from_slice(&frame),
+                        },
+                        // No more frames will arrive; decode any buffered compressed
+                        // audio, then wait for the sink to drain.
+                        None => {
+                            if let Decoding::Compressed = decoding {
+                                let buffered = std::mem::take(&mut current.buffer);
+                                if !buffered.is_empty() {
+                                    match rodio::Decoder::new(Cursor::new(buffered)) {
+                                        Ok(source) => sink.append(source),
+                                        Err(e) => tracing::error!(?e, "failed to decode track"),
+                                    }
+                                }
+                            }
+                            current.frames_done = true;
+                        }
+                    },
+                    cue = current.track.metadata.recv(), if !current.meta_done => match cue {
+                        Some(cue) => {
+                            let _ = events.send(PlayerEvent::Cue {
+                                request_id: current.request_id.clone(),
+                                offset: cue.offset,
+                                payload: cue.payload,
+                            });
+                        }
+                        None => current.meta_done = true,
+                    },
+                    _ = tokio::time::sleep(POSITION_INTERVAL) => {
+                        if current.frames_done && sink.empty() {
+                            let _ = events.send(PlayerEvent::Finished {
+                                request_id: current.request_id.clone(),
+                            });
+                            active = None;
+                        } else {
+                            let _ = events.send(PlayerEvent::Position {
+                                request_id: current.request_id.clone(),
+                                offset: current.offset(&sink),
+                            });
+                        }
+                    }
+                }
+            }
+            // Idle: wait for a command (or a newly enqueued track) without spinning.
+            None => {
+                tokio::select! {
+                    command = commands.recv() => match command {
+                        None => break,
+                        // `play` clears a pause left over from an earlier track.
+                        Some(Command::Play) => sink.play(),
+                        // Any command re-checks the queue on the next iteration.
+                        Some(_) => {}
+                    },
+                    _ = tokio::time::sleep(POSITION_INTERVAL) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Wrap a raw 16-bit PCM frame as a rodio source so it can be appended to the
+/// sink the moment it arrives.
+fn pcm_source(frame: &[u8], sample_rate: u32, channels: u16) -> rodio::buffer::SamplesBuffer<i16> {
+    let samples: Vec<i16> = frame
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    rodio::buffer::SamplesBuffer::new(channels, sample_rate, samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sample_rate() {
+        assert_eq!(parse_sample_rate("raw-24khz-16bit-mono-pcm"), Some(24_000));
+        assert_eq!(parse_sample_rate("raw-8khz-16bit-mono-pcm"), Some(8_000));
+        assert_eq!(parse_sample_rate("raw-22050hz-16bit-mono-pcm"), Some(22_050));
+        assert_eq!(parse_sample_rate("audio-24khz-48kbitrate-mono-mp3"), Some(24_000));
+        assert_eq!(parse_sample_rate("no-rate-here"), None);
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Policy governing how a dropped streaming session is re-established.
+///
+/// Configured on [`Config`] via `reconnect(...)`. Backoff grows exponentially
+/// from `base_delay` (capped at `max_delay`) with full jitter, and the whole
+/// resume effort is abandoned once `max_attempts` is reached or `deadline`
+/// elapses, whichever comes first.
+#[derive(Clone, Debug)]
+pub struct ReconnectPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub deadline: Option<Duration>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            deadline: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// A policy that never attempts to resume.
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 0,
+            ..Self::default()
+        }
+    }
+
+    /// The delay to wait before `attempt` (1-based), with full jitter applied to
+    /// the exponentially growing base.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exp = attempt.saturating_sub(1).min(31);
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1u32 << exp)
+            .min(self.max_delay);
+        let millis = ceiling.as_millis() as u64;
+        if millis == 0 {
+            return Duration::ZERO;
+        }
+        // Full jitter in [0, ceiling]; a coarse process-clock sample is enough
+        // entropy to keep reconnecting clients from synchronizing.
+        let sample = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos() as u64)
+            .unwrap_or(0);
+        Duration::from_millis(sample % (millis + 1))
+    }
+}
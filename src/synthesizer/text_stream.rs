@@ -1,24 +1,66 @@
 use crate::connector::Client as BaseClient;
+use crate::synthesizer::reconnect::ReconnectPolicy;
 use crate::synthesizer::utils::{create_text_message, create_turn_end_message};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio_websockets::Message;
 
 const MAX_TEXT_FRAME_BYTES: usize = 4096;
 
+/// Replay log kept for a streaming session so it can be rebuilt after a
+/// transport drop. Holds the handshake messages (`speech.config` and
+/// `synthesis.context`) and every text chunk not yet acknowledged by a
+/// boundary event's reported offset.
+#[derive(Default)]
+struct ResumeLog {
+    speech_config: Option<Message>,
+    synthesis_context: Option<Message>,
+    /// Chunks not yet confirmed by the service, each tagged with the cumulative
+    /// character offset at its end so they can be acknowledged by the boundary
+    /// offsets the service reports rather than one-per-event.
+    unacked: VecDeque<(usize, String)>,
+    /// Running count of characters written through this stream.
+    written_chars: usize,
+}
+
 #[derive(Clone)]
 pub struct TextStream {
     client: BaseClient,
     request_id: String,
+    log: Arc<Mutex<ResumeLog>>,
+    policy: ReconnectPolicy,
 }
 
 impl TextStream {
-    pub(crate) fn new(client: BaseClient, request_id: String) -> Self {
-        Self { client, request_id }
+    pub(crate) fn new(
+        client: BaseClient,
+        request_id: String,
+        speech_config: Message,
+        synthesis_context: Message,
+        policy: ReconnectPolicy,
+    ) -> Self {
+        Self {
+            client,
+            request_id,
+            log: Arc::new(Mutex::new(ResumeLog {
+                speech_config: Some(speech_config),
+                synthesis_context: Some(synthesis_context),
+                unacked: VecDeque::new(),
+                written_chars: 0,
+            })),
+            policy,
+        }
     }
 
     /// Write text to the streaming request. Large inputs are chunked to <= 4096 bytes.
+    ///
+    /// Each chunk is recorded as unacknowledged until a boundary offset passed to
+    /// `acknowledge_through` reaches its end, so it can be replayed verbatim on resume.
     pub async fn write(&self, text: &str) -> crate::Result<()> {
         for chunk in Utf8Chunker::new(text.as_bytes(), MAX_TEXT_FRAME_BYTES) {
             let chunk_str = std::str::from_utf8(chunk)
                 .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+            self.record_unacked(chunk_str);
             self.client
                 .send(create_text_message(self.request_id.clone(), chunk_str))
                 .await?;
@@ -32,6 +74,70 @@ impl TextStream {
             .send(create_turn_end_message(self.request_id.clone()))
             .await
     }
+
+    /// Acknowledge every chunk the service has fully consumed up to `chars`, the
+    /// cumulative character offset reported by a word/sentence-boundary event.
+    ///
+    /// Boundary events do not arrive one-per-written-chunk, so the replay window
+    /// is trimmed by offset rather than by popping a single chunk per event: a
+    /// chunk is dropped once its end offset is within the acknowledged range.
+    pub(crate) fn acknowledge_through(&self, chars: usize) {
+        if let Ok(mut log) = self.log.lock() {
+            while log
+                .unacked
+                .front()
+                .is_some_and(|(end, _)| *end <= chars)
+            {
+                log.unacked.pop_front();
+            }
+        }
+    }
+
+    /// Re-establish the session over `client` under the *same* `request_id`:
+    /// replay the config/context handshake followed by every unacknowledged
+    /// chunk. Returns once the stream is caught up to where the drop occurred.
+    pub(crate) async fn resume(&mut self, client: BaseClient) -> crate::Result<()> {
+        self.client = client;
+
+        let (config, context, pending) = {
+            let log = self
+                .log
+                .lock()
+                .map_err(|_| crate::Error::InternalError("resume log poisoned".to_string()))?;
+            (
+                log.speech_config.clone(),
+                log.synthesis_context.clone(),
+                log.unacked.iter().cloned().collect::<Vec<_>>(),
+            )
+        };
+
+        if let Some(config) = config {
+            self.client.send(config).await?;
+        }
+        if let Some(context) = context {
+            self.client.send(context).await?;
+        }
+        for (_, chunk) in pending {
+            self.client
+                .send(create_text_message(self.request_id.clone(), &chunk))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// The reconnect policy in force for this stream.
+    pub(crate) fn policy(&self) -> &ReconnectPolicy {
+        &self.policy
+    }
+
+    fn record_unacked(&self, chunk: &str) {
+        if let Ok(mut log) = self.log.lock() {
+            log.written_chars += chunk.chars().count();
+            let end = log.written_chars;
+            log.unacked.push_back((end, chunk.to_string()));
+        }
+    }
 }
 
 /// Iterator that yields UTF-8 safe slices not exceeding `limit` bytes.
@@ -74,5 +180,3 @@ impl<'a> Iterator for Utf8Chunker<'a> {
         Some(chunk)
     }
 }
-
-